@@ -25,14 +25,17 @@ const USAGE: &'static str = r#"
 Parse Godot source and generate JSON API description.
 
 Usage:
-	gdrs-parse [-o <output>] [-I <include> | -D <define>]... <file>...
+	gdrs-parse [-o <output>] [--diagnostics <path>] [--compile-commands <path>] [--report <path>] [-I <include> | -D <define>]... <file>...
 	gdrs-parse --help
 
 Options:
-	-I <include>  Add an #include search path
-	-D <define>   Define a preprocessor symbol
-	-o <output>   Output file [default: -]
-	-h, --help    Show this message
+	-I <include>              Add an #include search path
+	-D <define>               Define a preprocessor symbol
+	-o <output>                Output file [default: -]
+	--diagnostics <path>       Write parse diagnostics as a JSON array to <path> instead of stderr
+	--compile-commands <path>  Look up per-file -I/-D/-std flags from a compile_commands.json directory
+	--report <path>            Write an unresolved-symbol coverage report as JSON to <path>
+	-h, --help                 Show this message
 "#;
 
 
@@ -45,12 +48,82 @@ enum ParseError {
 
 
 
+#[derive(Clone, Serialize)]
+enum Severity {
+	Warning,
+	Error,
+}
+
+
+
+#[derive(Clone, PartialEq, Serialize)]
+enum DiagnosticCategory {
+	UnsupportedType,
+	UnsupportedParam,
+	UnsupportedReturn,
+	UnsupportedGlobal,
+	UnsupportedField,
+	UnsupportedTemplateParam,
+	AnonymousNamespace,
+	MissingCompileCommand,
+}
+
+
+
+#[derive(Clone, Serialize)]
+struct Diagnostic {
+	severity: Severity,
+	category: DiagnosticCategory,
+	message: String,
+	file: String,
+	line: u32,
+	column: u32,
+	entity_spelling: String,
+}
+
+
+
+fn record_diagnostic(diagnostics: &mut Vec<Diagnostic>, e: clang::Entity, severity: Severity, category: DiagnosticCategory, message: String) {
+	let loc = e.get_location().unwrap().get_expansion_location();
+	diagnostics.push(Diagnostic{
+		severity: severity,
+		category: category,
+		message: message,
+		file: loc.file.get_path().to_string_lossy().into_owned(),
+		line: loc.line,
+		column: loc.column,
+		entity_spelling: e.get_name().unwrap_or_else(String::new),
+	});
+}
+
+
+
+fn emit_diagnostics(diagnostics: &[Diagnostic], path: Option<String>) {
+	match path {
+		Some(path) => {
+			let json = serde_json::to_string_pretty(diagnostics).unwrap();
+			let mut file = fs::File::create(path::Path::new(&path)).unwrap();
+			write!(file, "{}", json).unwrap();
+		},
+		None => {
+			for d in diagnostics {
+				let _ = writeln!(io::stderr(), "WARNING: {} `{}`: {}:{}:{}", d.message, d.entity_spelling, d.file, d.line, d.column);
+			}
+		},
+	}
+}
+
+
+
 #[derive(RustcDecodable)]
 #[allow(non_snake_case)]
 struct Args {
 	pub flag_o: String,
 	pub flag_I: Option<Vec<String>>,
 	pub flag_D: Option<Vec<String>>,
+	pub flag_diagnostics: Option<String>,
+	pub flag_compile_commands: Option<String>,
+	pub flag_report: Option<String>,
 	pub flag_help: bool,
 	pub arg_file: Vec<String>,
 }
@@ -58,8 +131,8 @@ struct Args {
 
 
 fn main() {
-	let (output, flags, files) = {
-		let Args{flag_o: output, flag_I: includes, flag_D: defines, flag_help: help, arg_file: files} = Docopt::new(USAGE)
+	let (output, diagnostics_path, compile_commands, report_path, flags, files) = {
+		let Args{flag_o: output, flag_I: includes, flag_D: defines, flag_diagnostics: diagnostics_path, flag_compile_commands: compile_commands, flag_report: report_path, flag_help: help, arg_file: files} = Docopt::new(USAGE)
 			.and_then(|d| d.argv(env::args().into_iter()).decode())
 			.unwrap_or_else(|e| e.exit());
 
@@ -76,9 +149,11 @@ fn main() {
 			flags.extend(defines.into_iter().map(|d| format!("-D{}", d)));
 		}
 
-		(output, flags, files)
+		(output, diagnostics_path, compile_commands, report_path, flags, files)
 	};
 
+	let mut diagnostics = Vec::new();
+
 	let c = clang::Clang::new().unwrap();
 
 	let mut index = clang::Index::new(&c, true, false);
@@ -94,18 +169,22 @@ fn main() {
 		namespaces: Vec::new(),
 	};
 
+	let db = compile_commands.map(|dir| clang::CompilationDatabase::from_directory(dir).unwrap());
+
 	let mut tus = Vec::new();
 	for file_pat in &files {
 		for file in glob::glob(file_pat).unwrap() {
 			let file = file.unwrap();
 
+			let file_flags = file_flags(&db, &file, &flags, &mut diagnostics);
+
 			let mut parser = index.parser(file);
-			parser.arguments(&flags);
+			parser.arguments(&file_flags);
 			//let parser = parser.detailed_preprocessing_record(true);
 			let parser = parser.skip_function_bodies(true);
 
 			let tu = parser.parse().unwrap();
-			if let Some(ns) = parse_namespace(tu.get_entity()) {
+			if let Some(ns) = parse_namespace(tu.get_entity(), &mut diagnostics) {
 				tus.push(ns);
 			}
 		}
@@ -122,11 +201,270 @@ fn main() {
 		let mut file = fs::File::create(path::Path::new(&output)).unwrap();
 		write!(file, "{}", json).unwrap();
 	}
+
+	emit_diagnostics(&diagnostics, diagnostics_path);
+
+	if let Some(report_path) = report_path {
+		let report = build_report(&api, &diagnostics);
+		let json = serde_json::to_string_pretty(&report).unwrap();
+		let mut file = fs::File::create(path::Path::new(&report_path)).unwrap();
+		write!(file, "{}", json).unwrap();
+	}
+}
+
+
+
+fn file_flags(db: &Option<clang::CompilationDatabase>, file: &path::Path, fallback: &[String], diagnostics: &mut Vec<Diagnostic>) -> Vec<String> {
+	let db = match *db {
+		Some(ref db) => db,
+		None => return fallback.to_vec(),
+	};
+
+	let canonical = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+
+	let command = db.get_compile_commands(&canonical).ok().and_then(|commands| commands.get_commands().into_iter().next());
+	let command = match command {
+		Some(command) => command,
+		None => {
+			diagnostics.push(Diagnostic{
+				severity: Severity::Warning,
+				category: DiagnosticCategory::MissingCompileCommand,
+				message: "No matching compile_commands.json entry; falling back to global flags".to_string(),
+				file: canonical.to_string_lossy().into_owned(),
+				line: 0,
+				column: 0,
+				entity_spelling: String::new(),
+			});
+			return fallback.to_vec();
+		},
+	};
+
+	let dir = command.get_directory();
+
+	let mut flags = vec!["-xc++".to_string()];
+	let mut args = command.get_arguments().into_iter();
+	while let Some(arg) = args.next() {
+		if arg == "-I" {
+			if let Some(val) = args.next() {
+				flags.push(format!("-I{}", resolve_include(&dir, &val)));
+			}
+		} else if arg == "-D" {
+			if let Some(val) = args.next() {
+				flags.push(format!("-D{}", val));
+			}
+		} else if arg.starts_with("-I") {
+			flags.push(format!("-I{}", resolve_include(&dir, &arg[2..])));
+		} else if arg.starts_with("-D") || arg.starts_with("-std") {
+			flags.push(arg);
+		}
+	}
+
+	flags
+}
+
+
+
+fn resolve_include(dir: &path::Path, include: &str) -> String {
+	let include = path::Path::new(include);
+	if include.is_relative() {
+		dir.join(include).to_string_lossy().into_owned()
+	} else {
+		include.to_string_lossy().into_owned()
+	}
+}
+
+
+
+#[derive(Serialize)]
+struct CategoryCoverage {
+	category: DiagnosticCategory,
+	count: usize,
+	examples: Vec<String>,
+}
+
+
+
+#[derive(Serialize)]
+struct CoverageReport {
+	categories: Vec<CategoryCoverage>,
+	dangling_references: Vec<Vec<String>>,
+}
+
+
+
+fn build_report(api: &gdrs_api::Namespace, diagnostics: &[Diagnostic]) -> CoverageReport {
+	let all_categories = [
+		DiagnosticCategory::UnsupportedType,
+		DiagnosticCategory::UnsupportedParam,
+		DiagnosticCategory::UnsupportedReturn,
+		DiagnosticCategory::UnsupportedGlobal,
+		DiagnosticCategory::UnsupportedField,
+		DiagnosticCategory::UnsupportedTemplateParam,
+		DiagnosticCategory::AnonymousNamespace,
+		DiagnosticCategory::MissingCompileCommand,
+	];
+
+	let mut categories = Vec::new();
+	for category in &all_categories {
+		let matching: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.category == *category).collect();
+		if matching.is_empty() {
+			continue;
+		}
+
+		categories.push(CategoryCoverage{
+			category: category.clone(),
+			count: matching.len(),
+			examples: matching.iter().take(5).map(|d| d.message.clone()).collect(),
+		});
+	}
+
+	let mut defined = std::collections::HashSet::new();
+	collect_defined(api, &[], &mut defined);
+
+	let mut refs = Vec::new();
+	collect_namespace_refs(api, &mut refs);
+
+	let mut seen = std::collections::HashSet::new();
+	let mut dangling_references = Vec::new();
+	for r in refs {
+		if !defined.contains(&r) && seen.insert(r.clone()) {
+			dangling_references.push(r);
+		}
+	}
+
+	CoverageReport{
+		categories: categories,
+		dangling_references: dangling_references,
+	}
+}
+
+
+
+fn collect_defined(ns: &gdrs_api::Namespace, prefix: &[String], defined: &mut std::collections::HashSet<Vec<String>>) {
+	let mut ns_path = prefix.to_vec();
+	if !ns.name.is_empty() {
+		ns_path.push(ns.name.clone());
+	}
+
+	for e in &ns.enums {
+		let mut p = ns_path.clone();
+		p.push(e.name.clone());
+		defined.insert(p);
+	}
+	for a in &ns.aliases {
+		let mut p = ns_path.clone();
+		p.push(a.name.clone());
+		defined.insert(p);
+	}
+	for c in &ns.classes {
+		let mut p = ns_path.clone();
+		p.push(c.name.clone());
+		collect_defined_class(c, &p, defined);
+		defined.insert(p);
+	}
+	for n in &ns.namespaces {
+		collect_defined(n, &ns_path, defined);
+	}
+}
+
+
+
+fn collect_defined_class(c: &gdrs_api::Class, prefix: &[String], defined: &mut std::collections::HashSet<Vec<String>>) {
+	for e in &c.enums {
+		let mut p = prefix.to_vec();
+		p.push(e.name.clone());
+		defined.insert(p);
+	}
+	for a in &c.aliases {
+		let mut p = prefix.to_vec();
+		p.push(a.name.clone());
+		defined.insert(p);
+	}
+}
+
+
+
+fn collect_refs_from_type(ty: &gdrs_api::TypeRef, refs: &mut Vec<Vec<String>>) {
+	match ty.name {
+		gdrs_api::TypeName::TypeName(ref path) => refs.push(path.clone()),
+		gdrs_api::TypeName::Class(ref path, ref params) => {
+			refs.push(path.clone());
+			for p in params {
+				collect_refs_from_type(p, refs);
+			}
+		},
+		_ => (),
+	}
+}
+
+
+
+fn collect_function_refs(f: &gdrs_api::Function, refs: &mut Vec<Vec<String>>) {
+	for p in &f.params {
+		collect_refs_from_type(&p.ty, refs);
+	}
+	if let Some(ref r) = f.return_ty {
+		collect_refs_from_type(r, refs);
+	}
+}
+
+
+
+fn collect_class_refs(c: &gdrs_api::Class, refs: &mut Vec<Vec<String>>) {
+	for b in &c.bases {
+		refs.push(b.name_path.clone());
+	}
+	for tp in &c.template_params {
+		if let Some(ref ty) = tp.ty {
+			collect_refs_from_type(ty, refs);
+		}
+	}
+	for cst in &c.consts {
+		collect_refs_from_type(&cst.ty, refs);
+	}
+	for f in &c.fields {
+		collect_refs_from_type(&f.ty, refs);
+	}
+	for e in &c.enums {
+		collect_refs_from_type(&e.underlying, refs);
+	}
+	for a in &c.aliases {
+		collect_refs_from_type(&a.ty, refs);
+	}
+	for m in &c.methods {
+		collect_function_refs(m, refs);
+	}
+}
+
+
+
+fn collect_namespace_refs(ns: &gdrs_api::Namespace, refs: &mut Vec<Vec<String>>) {
+	for cst in &ns.consts {
+		collect_refs_from_type(&cst.ty, refs);
+	}
+	for g in &ns.globals {
+		collect_refs_from_type(&g.ty, refs);
+	}
+	for e in &ns.enums {
+		collect_refs_from_type(&e.underlying, refs);
+	}
+	for a in &ns.aliases {
+		collect_refs_from_type(&a.ty, refs);
+	}
+	for c in &ns.classes {
+		collect_class_refs(c, refs);
+	}
+	for f in &ns.functions {
+		collect_function_refs(f, refs);
+	}
+	for n in &ns.namespaces {
+		collect_namespace_refs(n, refs);
+	}
 }
 
 
 
-fn parse_namespace(e: clang::Entity) -> Option<gdrs_api::Namespace> {
+fn parse_namespace(e: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> Option<gdrs_api::Namespace> {
 	let name = e.get_name();
 	if let None = name {
 		return None;
@@ -157,32 +495,35 @@ fn parse_namespace(e: clang::Entity) -> Option<gdrs_api::Namespace> {
 				if c.get_type().unwrap().is_const_qualified() {
 					if let Some(val) = c.get_child(0).and_then(|exp| parse_value(exp)) {
 						ns.consts.push(gdrs_api::Const{
-							ty: parse_type(c.get_type().unwrap()).or_else(|_| parse_type(c.get_child(0).unwrap().get_type().unwrap())).unwrap(),
+							ty: parse_type(c.get_type().unwrap(), c, diagnostics).or_else(|_| parse_type(c.get_child(0).unwrap().get_type().unwrap(), c, diagnostics)).unwrap(),
 							name: c.get_name().unwrap(),
+							doc: parse_doc(c),
 							value: val,
 						})
 					}
 				} else if c.get_storage_class() == Some(clang::StorageClass::Extern) {
-					match parse_type(c.get_type().unwrap()) {
+					match parse_type(c.get_type().unwrap(), c, diagnostics) {
 						Ok(ty) => ns.globals.push(gdrs_api::Global{
 							ty: ty,
 							name: c.get_name().unwrap(),
+							doc: parse_doc(c),
 						}),
 						Err(ParseError::Unsupported) => {
-							let _ = writeln!(io::stderr(), "WARNING: Unsupported extern global `{}`: {:?}", c.get_name().unwrap(), c);
+							record_diagnostic(diagnostics, c, Severity::Warning, DiagnosticCategory::UnsupportedGlobal, "Unsupported extern global".to_string());
 						},
 						_ => (),
 					}
 				}
 			},
 			clang::EntityKind::EnumDecl => {
-				let _enum = parse_enum(&c);
+				let _enum = parse_enum(&c, diagnostics);
 				if _enum.name == "const" {
 					let gdrs_api::Enum{variants, underlying, ..} = _enum;
 					for v in variants.into_iter() {
 						ns.consts.push(gdrs_api::Const{
 							ty: underlying.clone(),
 							name: v.name,
+							doc: v.doc,
 							value: v.value,
 						});
 					}
@@ -191,22 +532,22 @@ fn parse_namespace(e: clang::Entity) -> Option<gdrs_api::Namespace> {
 				}
 			},
 			clang::EntityKind::TypeAliasDecl | clang::EntityKind::TypedefDecl => {
-				if let Some(alias) = parse_alias(c) {
+				if let Some(alias) = parse_alias(c, diagnostics) {
 					ns.aliases.push(alias);
 				}
 			},
 			clang::EntityKind::ClassDecl => {
-				let mut class = parse_class(c);
+				let mut class = parse_class(c, diagnostics);
 				class.include = loc.to_string_lossy().into_owned();
 				ns.classes.push(class);
 			},
 			clang::EntityKind::FunctionDecl => {
-				if let Some(func) = parse_function(c) {
+				if let Some(func) = parse_function(c, diagnostics) {
 					ns.functions.push(func);
 				}
 			},
 			clang::EntityKind::Namespace => {
-				if let Some(cns) = parse_namespace(c) {
+				if let Some(cns) = parse_namespace(c, diagnostics) {
 					if let Some(dns) = ns.namespaces.iter_mut().find(|dns| dns.name == cns.name) {
 						merge_namespace(dns, cns);
 						return clang::EntityVisitResult::Continue;
@@ -271,10 +612,11 @@ fn merge_namespace(dst: &mut gdrs_api::Namespace, src: gdrs_api::Namespace) {
 
 
 
-fn parse_enum(e: &clang::Entity) -> gdrs_api::Enum {
-	let underlying = parse_type(e.get_enum_underlying_type().unwrap()).unwrap();
+fn parse_enum(e: &clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> gdrs_api::Enum {
+	let underlying = parse_type(e.get_enum_underlying_type().unwrap(), *e, diagnostics).unwrap();
 	let mut _enum = gdrs_api::Enum{
 		name: e.get_name().unwrap_or_else(|| "const".to_string()),
+		doc: parse_doc(*e),
 		underlying: underlying.clone(),
 		variants: Vec::new(),
 	};
@@ -282,6 +624,7 @@ fn parse_enum(e: &clang::Entity) -> gdrs_api::Enum {
 	e.visit_children(|c, _| {
 		_enum.variants.push(gdrs_api::Variant{
 			name: c.get_name().unwrap(),
+			doc: parse_doc(c),
 			value: match _enum.underlying.name {
 				gdrs_api::TypeName::Char | gdrs_api::TypeName::Short | gdrs_api::TypeName::Int | gdrs_api::TypeName::Long | gdrs_api::TypeName::LongLong
 					=> gdrs_api::Value::Int(c.get_enum_constant_value().map(|(v, _)| v).unwrap()),
@@ -299,14 +642,20 @@ fn parse_enum(e: &clang::Entity) -> gdrs_api::Enum {
 
 
 
-fn parse_alias(e: clang::Entity) -> Option<gdrs_api::TypeAlias> {
-	match parse_type(e.get_typedef_underlying_type().unwrap()) {
+fn parse_doc(e: clang::Entity) -> Option<String> {
+	e.get_comment()
+}
+
+
+
+fn parse_alias(e: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> Option<gdrs_api::TypeAlias> {
+	match parse_type(e.get_typedef_underlying_type().unwrap(), e, diagnostics) {
 		Ok(ty) => Some(gdrs_api::TypeAlias{
 			name: e.get_name().unwrap(),
 			ty: ty,
 		}),
 		Err(ParseError::Unsupported) => {
-			let _ = writeln!(io::stderr(), "WARNING: Unsupported type alias `{}`: {:?}", e.get_name().unwrap(), e);
+			record_diagnostic(diagnostics, e, Severity::Warning, DiagnosticCategory::UnsupportedType, "Unsupported type alias".to_string());
 			None
 		},
 		Err(ParseError::Ignored) => None,
@@ -315,10 +664,13 @@ fn parse_alias(e: clang::Entity) -> Option<gdrs_api::TypeAlias> {
 
 
 
-fn parse_class(e: clang::Entity) -> gdrs_api::Class {
+fn parse_class(e: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> gdrs_api::Class {
 	let mut class = gdrs_api::Class{
 		include: String::new(),
 		name: e.get_name().unwrap(),
+		doc: parse_doc(e),
+		template_params: Vec::with_capacity(0),
+		bases: Vec::with_capacity(0),
 		consts: Vec::with_capacity(0),
 		enums: Vec::with_capacity(0),
 		aliases: Vec::with_capacity(0),
@@ -327,6 +679,24 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 	};
 
 	e.visit_children(|c, _| {
+		match c.get_kind() {
+			clang::EntityKind::TemplateTypeParameter => {
+				class.template_params.push(gdrs_api::TemplateParam{
+					name: c.get_name().unwrap_or_else(String::new),
+					ty: None,
+				});
+				return clang::EntityVisitResult::Continue;
+			},
+			clang::EntityKind::NonTypeTemplateParameter => {
+				class.template_params.push(gdrs_api::TemplateParam{
+					name: c.get_name().unwrap_or_else(String::new),
+					ty: parse_type(c.get_type().unwrap(), c, diagnostics).ok(),
+				});
+				return clang::EntityVisitResult::Continue;
+			},
+			_ => (),
+		}
+
 		let access = c.get_accessibility().unwrap();
 		if access == clang::Accessibility::Private {
 			return clang::EntityVisitResult::Continue
@@ -334,13 +704,14 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 
 		match c.get_kind() {
 			clang::EntityKind::EnumDecl => {
-				let _enum = parse_enum(&c);
+				let _enum = parse_enum(&c, diagnostics);
 				if _enum.name == "const" {
 					let gdrs_api::Enum{variants, ..} = _enum;
 					for v in variants.into_iter() {
 						class.consts.push(gdrs_api::Const{
 							ty: _enum.underlying.clone(),
 							name: v.name,
+							doc: v.doc,
 							value: v.value,
 						});
 					}
@@ -349,7 +720,7 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 				}
 			},
 			clang::EntityKind::TypeAliasDecl | clang::EntityKind::TypedefDecl => {
-				if let Some(alias) = parse_alias(c) {
+				if let Some(alias) = parse_alias(c, diagnostics) {
 					class.aliases.push(alias);
 				}
 			},
@@ -357,16 +728,17 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 				if c.get_type().unwrap().is_const_qualified() {
 					if let Some(val) = c.get_child(0).and_then(|exp| parse_value(exp)) {
 						class.consts.push(gdrs_api::Const{
-							ty: parse_type(c.get_type().unwrap()).or_else(|_| parse_type(c.get_child(0).unwrap().get_type().unwrap())).unwrap(),
+							ty: parse_type(c.get_type().unwrap(), c, diagnostics).or_else(|_| parse_type(c.get_child(0).unwrap().get_type().unwrap(), c, diagnostics)).unwrap(),
 							name: c.get_name().unwrap(),
+							doc: parse_doc(c),
 							value: val,
 						})
 					}
 				} else {
-					let ty = match parse_type(c.get_type().unwrap()) {
+					let ty = match parse_type(c.get_type().unwrap(), c, diagnostics) {
 						Ok(ty) => ty,
 						Err(ParseError::Unsupported) => {
-							let _ = writeln!(io::stderr(), "WARNING: Unsupported field `{:?}`: {:?}", c.get_type().unwrap(), c);
+							record_diagnostic(diagnostics, c, Severity::Warning, DiagnosticCategory::UnsupportedField, format!("Unsupported field `{:?}`", c.get_type().unwrap()));
 							return clang::EntityVisitResult::Continue;
 						},
 						Err(ParseError::Ignored) => return clang::EntityVisitResult::Continue,
@@ -376,15 +748,32 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 						access: if let clang::Accessibility::Protected = access { gdrs_api::Access::Protected } else { gdrs_api::Access::Public },
 						is_static: c.get_storage_class() == Some(clang::StorageClass::Static),
 						name: c.get_name().unwrap(),
+						doc: parse_doc(c),
 						ty: ty,
 					});
 				}
 			},
 			clang::EntityKind::Method => {
-				if let Some(method) = parse_function(c) {
+				if let Some(method) = parse_function(c, diagnostics) {
 					class.methods.push(method);
 				}
 			},
+			clang::EntityKind::BaseSpecifier => {
+				if let Some(decl) = c.get_type().unwrap().get_declaration() {
+					let base_loc = decl.get_location().unwrap().get_expansion_location().file.get_path();
+					if base_loc.extension() == Some(OsStr::new("cpp")) || base_loc.components().any(|comp| comp == path::Component::Normal(OsStr::new("thirdparty"))) {
+						return clang::EntityVisitResult::Continue;
+					}
+
+					if let Ok(name_path) = entity_name_path(decl, c, diagnostics) {
+						class.bases.push(gdrs_api::BaseClass{
+							name_path: name_path,
+							access: if let clang::Accessibility::Protected = access { gdrs_api::Access::Protected } else { gdrs_api::Access::Public },
+							is_virtual: c.is_virtual_base(),
+						});
+					}
+				}
+			},
 			_ => (),
 		}
 
@@ -396,21 +785,22 @@ fn parse_class(e: clang::Entity) -> gdrs_api::Class {
 
 
 
-fn parse_function(e: clang::Entity) -> Option<gdrs_api::Function> {
+fn parse_function(e: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> Option<gdrs_api::Function> {
 	let ty = e.get_type().unwrap();
 	let result = ty.get_result_type().unwrap();
 
 	Some(gdrs_api::Function{
 		name: e.get_name().unwrap(),
+		doc: parse_doc(e),
 		params: {
 			if let Some(params) = e.get_arguments()
-				.map(|vp| vp.into_iter().map(|p| (parse_type(p.get_type().unwrap()), p.get_name().unwrap_or_else(|| "".to_string()), p.get_child(0)))
+				.map(|vp| vp.into_iter().map(|p| (parse_type(p.get_type().unwrap(), p, diagnostics), p.get_name().unwrap_or_else(|| "".to_string()), p.get_child(0)))
 				.collect::<Vec<_>>())
 			{
 				if let Some(i) = params.iter().position(|&(ref p, _, _)| p.is_err()) {
 					let param = e.get_arguments().unwrap()[i];
 					if params[i].0.as_ref().unwrap_err() == &ParseError::Unsupported {
-						let _ = writeln!(io::stderr(), "WARNING: Unsupported param `{:?}`: {:?}", param, e);
+						record_diagnostic(diagnostics, param, Severity::Warning, DiagnosticCategory::UnsupportedParam, format!("Unsupported param `{:?}`", param.get_type().unwrap()));
 					}
 					return None;
 				}
@@ -425,10 +815,10 @@ fn parse_function(e: clang::Entity) -> Option<gdrs_api::Function> {
 			}
 		},
 		return_ty: if result.get_kind() == clang::TypeKind::Void { None } else {
-			match parse_type(result) {
+			match parse_type(result, e, diagnostics) {
 				Ok(r) => Some(r),
 				Err(ParseError::Unsupported) => {
-					let _ = writeln!(io::stderr(), "WARNING: Unsupported return `{:?}`: {:?}", result, e);
+					record_diagnostic(diagnostics, e, Severity::Warning, DiagnosticCategory::UnsupportedReturn, format!("Unsupported return `{:?}`", result));
 					return None;
 				},
 				_ => return None,
@@ -450,7 +840,31 @@ fn parse_function(e: clang::Entity) -> Option<gdrs_api::Function> {
 
 
 
-fn parse_type(mut t: clang::Type) -> Result<gdrs_api::TypeRef, ParseError> {
+fn entity_name_path(decl: clang::Entity, site: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> Result<Vec<String>, ParseError> {
+	let mut p = decl;
+	let mut name_path = Vec::new();
+	name_path.push(p.get_name().unwrap());
+	loop {
+		p = p.get_semantic_parent().unwrap();
+		match p.get_kind() {
+			clang::EntityKind::Namespace | clang::EntityKind::ClassDecl => {
+				if let Some(comp) = p.get_name() {
+					name_path.insert(0, comp);
+				} else {
+					record_diagnostic(diagnostics, site, Severity::Warning, DiagnosticCategory::AnonymousNamespace, "Unsupported anonymous namespace".to_string());
+					return Err(ParseError::Ignored);
+				}
+			},
+			_ => break,
+		}
+	}
+
+	Ok(name_path)
+}
+
+
+
+fn parse_type(mut t: clang::Type, site: clang::Entity, diagnostics: &mut Vec<Diagnostic>) -> Result<gdrs_api::TypeRef, ParseError> {
 	t = t.get_elaborated_type().unwrap_or(t);
 
 	let semantic = match t.get_kind() {
@@ -488,11 +902,17 @@ fn parse_type(mut t: clang::Type) -> Result<gdrs_api::TypeRef, ParseError> {
 	Ok(gdrs_api::TypeRef{
 		name: match t.get_kind() {
 			clang::TypeKind::Auto
-			| clang::TypeKind::Unexposed
 			| clang::TypeKind::BlockPointer
 			| clang::TypeKind::MemberPointer
 			=> return Err(ParseError::Ignored),
 
+			clang::TypeKind::Unexposed => {
+				match t.get_declaration() {
+					Some(decl) if decl.get_kind() == clang::EntityKind::TemplateTypeParameter => gdrs_api::TypeName::TemplateParam(decl.get_name().unwrap_or_else(String::new)),
+					_ => return Err(ParseError::Ignored),
+				}
+			},
+
 			clang::TypeKind::Bool => gdrs_api::TypeName::Bool,
 			clang::TypeKind::CharS | clang::TypeKind::SChar => gdrs_api::TypeName::Char,
 			clang::TypeKind::CharU | clang::TypeKind::UChar => gdrs_api::TypeName::UChar,
@@ -511,34 +931,18 @@ fn parse_type(mut t: clang::Type) -> Result<gdrs_api::TypeRef, ParseError> {
 			clang::TypeKind::Void if semantic != gdrs_api::TypeSemantic::Value => gdrs_api::TypeName::Void,
 
 			k if k == clang::TypeKind::Enum || k == clang::TypeKind::Typedef || k == clang::TypeKind::Record => {
-				let mut p = t.get_declaration().unwrap();
-				let mut name_path = Vec::new();
-				name_path.push(p.get_name().unwrap());
-				loop {
-					p = p.get_semantic_parent().unwrap();
-					match p.get_kind() {
-						clang::EntityKind::Namespace | clang::EntityKind::ClassDecl => {
-							if let Some(comp) = p.get_name() {
-								name_path.insert(0, comp);
-							} else {
-								let _ = writeln!(io::stderr(), "WARNING: Unsupported anonymous namespace");
-								return Err(ParseError::Ignored);
-							}
-						},
-						_ => break,
-					}
-				}
+				let name_path = entity_name_path(t.get_declaration().unwrap(), site, diagnostics)?;
 
 				match k {
 					clang::TypeKind::Enum | clang::TypeKind::Typedef => {
 						gdrs_api::TypeName::TypeName(name_path)
 					},
 					clang::TypeKind::Record => {
-						if let Some(params) = t.get_template_argument_types().map(|vp| vp.into_iter().map(|p| parse_type(p.unwrap())).collect::<Vec<_>>()) {
+						if let Some(params) = t.get_template_argument_types().map(|vp| vp.into_iter().map(|p| parse_type(p.unwrap(), site, diagnostics)).collect::<Vec<_>>()) {
 							if let Some(i) = params.iter().position(|p| p.is_err()) {
 								match *params[i].as_ref().unwrap_err() {
 									ParseError::Unsupported => {
-										let _ = writeln!(io::stderr(), "WARNING: Unsupported template param type `{:?}`", t.get_template_argument_types().unwrap()[i]);
+										record_diagnostic(diagnostics, site, Severity::Warning, DiagnosticCategory::UnsupportedTemplateParam, format!("Unsupported template param type `{:?}`", t.get_template_argument_types().unwrap()[i]));
 										return Err(ParseError::Unsupported);
 									},
 									ParseError::Ignored => return Err(ParseError::Ignored),
@@ -558,7 +962,7 @@ fn parse_type(mut t: clang::Type) -> Result<gdrs_api::TypeRef, ParseError> {
 			},
 
 			k => {
-				let _ = writeln!(io::stderr(), "WARNING: Unsupported type kind `{:?}`", k);
+				record_diagnostic(diagnostics, site, Severity::Error, DiagnosticCategory::UnsupportedType, format!("Unsupported type kind `{:?}`", k));
 				return Err(ParseError::Unsupported);
 			},
 		},